@@ -4,7 +4,7 @@
 //! numerical methods
 //!
 extern crate num;
-use num::{Float, Zero};
+use num::{Complex, Float, Zero};
 
 fn float_max<T>(a: T, b: T) -> T
 where
@@ -17,14 +17,150 @@ where
     }
 }
 
-fn float_min<T>(a: T, b: T) -> T
+/// Tests the relative condition `abs_diff <= rel_tol * max_abs_a_b` without ever
+/// forming the product `rel_tol * max_abs_a_b` when it would overflow to
+/// infinity.
+///
+/// If the product would overflow (which can only happen for large `max_abs_a_b`
+/// and `rel_tol > 1`), it silently drops the relative constraint and lets
+/// wildly different large numbers pass as "equal". We guard against that by
+/// testing the condition in divided form (`abs_diff / max_abs_a_b <= rel_tol`)
+/// instead, halving both operands first when `abs_diff` is itself near the top
+/// of the range. `max_abs_a_b` is always strictly positive when this is called.
+fn within_relative<T>(abs_diff: T, max_abs_a_b: T, rel_tol: T) -> bool
 where
     T: Float,
 {
-    if a >= b {
-        b
+    if max_abs_a_b > T::max_value() / rel_tol {
+        let two = T::one() + T::one();
+        if abs_diff > T::max_value() / two {
+            (abs_diff / two) / (max_abs_a_b / two) <= rel_tol
+        } else {
+            abs_diff / max_abs_a_b <= rel_tol
+        }
     } else {
-        a
+        abs_diff <= rel_tol * max_abs_a_b
+    }
+}
+
+/// Tolerant "nearly equal" comparison, exposed as a trait so it can be used
+/// generically in other numeric code as `a.is_close(b, rel_tol, abs_tol)`.
+///
+/// The free functions [`nearly_equal`] and friends delegate to this trait; the
+/// implementations for `f32`/`f64` carry the same semantics they have always
+/// had.
+///
+/// ## NaN handling
+///
+/// [`is_close`](IsClose::is_close) never treats a `NaN` as equal to anything,
+/// including another `NaN`. Some numerical test suites, however, deliberately
+/// expect `NaN` to propagate identically and want `NaN` to compare equal to
+/// `NaN`; [`is_close_with_nan`](IsClose::is_close_with_nan) provides that
+/// opt-in mode, returning `true` when both operands are `NaN` and otherwise
+/// behaving exactly like [`is_close`](IsClose::is_close).
+pub trait IsClose {
+    /// The scalar type in which tolerances for this value are expressed.
+    type Tol;
+
+    /// Returns `true` if and only if `self` is nearly equal to `other`, under
+    /// the given relative and absolute tolerances, with `NaN` never equal to
+    /// anything.
+    fn is_close(self, other: Self, rel_tol: Self::Tol, abs_tol: Self::Tol) -> bool;
+
+    /// Like [`is_close`](IsClose::is_close) but treats two `NaN`s as equal.
+    fn is_close_with_nan(self, other: Self, rel_tol: Self::Tol, abs_tol: Self::Tol) -> bool;
+}
+
+/// The tolerant comparison logic shared by the scalar [`IsClose`] impls.
+fn is_close_float<T>(a: T, b: T, rel_tol: T, abs_tol: T) -> bool
+where
+    T: Float + Zero,
+{
+    assert!(rel_tol > T::zero(), "relative tolerance nonpositive");
+    assert!(abs_tol > T::zero(), "absolute tolerance nonpositive");
+
+    let abs_a = a.abs();
+    let abs_b = b.abs();
+    let abs_diff = (a - b).abs();
+
+    if a.is_nan() || b.is_nan() {
+        false
+    } else if a == b || abs_diff <= T::min_positive_value() {
+        true
+    } else {
+        let max_abs_a_b = float_max(abs_a, abs_b);
+        abs_diff <= abs_tol && within_relative(abs_diff, max_abs_a_b, rel_tol)
+    }
+}
+
+/// The NaN-equality variant of [`is_close_float`].
+fn is_close_float_with_nan<T>(a: T, b: T, rel_tol: T, abs_tol: T) -> bool
+where
+    T: Float + Zero,
+{
+    if a.is_nan() && b.is_nan() {
+        return true;
+    }
+    is_close_float(a, b, rel_tol, abs_tol)
+}
+
+/// Implements [`IsClose`] for the primitive floating-point types.
+///
+/// `IsClose` is deliberately *not* implemented as a blanket over every
+/// [`Float`]: that would overlap with the dedicated `Complex<T>` impl below,
+/// since the compiler cannot rule out a future `Float` implementation for
+/// `Complex`.
+macro_rules! impl_is_close_float {
+    ($($t:ty),+ $(,)?) => {$(
+        impl IsClose for $t {
+            type Tol = $t;
+
+            fn is_close(self, other: $t, rel_tol: $t, abs_tol: $t) -> bool {
+                is_close_float(self, other, rel_tol, abs_tol)
+            }
+
+            fn is_close_with_nan(self, other: $t, rel_tol: $t, abs_tol: $t) -> bool {
+                is_close_float_with_nan(self, other, rel_tol, abs_tol)
+            }
+        }
+    )+};
+}
+
+impl_is_close_float!(f32, f64);
+
+impl<T> IsClose for Complex<T>
+where
+    T: Float + Zero,
+{
+    type Tol = T;
+
+    fn is_close(self, other: Complex<T>, rel_tol: T, abs_tol: T) -> bool {
+        assert!(rel_tol > T::zero(), "relative tolerance nonpositive");
+        assert!(abs_tol > T::zero(), "absolute tolerance nonpositive");
+
+        if self.re.is_nan() || self.im.is_nan() || other.re.is_nan() || other.im.is_nan() {
+            return false;
+        }
+
+        let abs_a = self.norm();
+        let abs_b = other.norm();
+        let abs_diff = (self - other).norm();
+
+        if self == other || abs_diff <= T::min_positive_value() {
+            true
+        } else {
+            let max_abs_a_b = float_max(abs_a, abs_b);
+            abs_diff <= abs_tol && within_relative(abs_diff, max_abs_a_b, rel_tol)
+        }
+    }
+
+    fn is_close_with_nan(self, other: Complex<T>, rel_tol: T, abs_tol: T) -> bool {
+        let self_nan = self.re.is_nan() || self.im.is_nan();
+        let other_nan = other.re.is_nan() || other.im.is_nan();
+        if self_nan && other_nan {
+            return true;
+        }
+        self.is_close(other, rel_tol, abs_tol)
     }
 }
 
@@ -49,32 +185,221 @@ where
 ///
 /// It works with `f64` and `f32`
 ///
+/// This is a thin wrapper around [`IsClose::is_close`].
+///
 /// ## Panics
 ///
 /// The function will panic if the specified relative or absolute tolerance is
 /// not positive.
 ///
-pub fn nearly_equal<T>(a: T, b: T, rel_tol: T, abs_tol: T) -> bool
+pub fn nearly_equal<T>(a: T, b: T, rel_tol: T::Tol, abs_tol: T::Tol) -> bool
 where
-    T: Float + Zero,
+    T: IsClose,
 {
-    assert!(rel_tol > T::zero(), "relative tolerance nonpositive");
-    assert!(abs_tol > T::zero(), "absolute tolerance nonpositive");
+    a.is_close(b, rel_tol, abs_tol)
+}
 
-    let abs_a = a.abs();
-    let abs_b = b.abs();
-    let abs_diff = (a - b).abs();
+/// Whether two numbers are nearly equal using a single tolerance `eps` for both
+/// the relative and absolute tolerance.
+///
+/// This is a thin convenience wrapper around [`nearly_equal`] for the common
+/// case where one does not want to spell out both tolerances.
+pub fn nearly_equal_eps<T>(a: T, b: T, eps: T::Tol) -> bool
+where
+    T: IsClose,
+    T::Tol: Copy,
+{
+    nearly_equal(a, b, eps, eps)
+}
 
-    if a.is_nan() || b.is_nan() {
-        false
-    } else if a == b || abs_diff <= T::min_positive_value() {
-        true
-    } else {
-        let max_abs_a_b = float_max(abs_a, abs_b);
-        abs_diff <= float_min(abs_tol, rel_tol * max_abs_a_b)
+/// Whether two numbers are nearly equal using the crate-level default
+/// tolerances for their type (see [`DefaultTolerance`]).
+///
+/// This is a thin convenience wrapper around [`nearly_equal`] for the common
+/// case where the default tolerances are good enough.
+pub fn nearly_equal_default<T>(a: T, b: T) -> bool
+where
+    T: IsClose,
+    T::Tol: DefaultTolerance,
+{
+    nearly_equal(a, b, T::Tol::default_rel_tol(), T::Tol::default_abs_tol())
+}
+
+/// Whether `a` is nearly less than `b` under the given tolerances
+///
+/// This is true if and only if `a < b` and `a` is *not* nearly equal to `b`,
+/// so that two values within tolerance are treated as equal rather than ordered.
+pub fn nearly_less<T>(a: T, b: T, rel_tol: T, abs_tol: T) -> bool
+where
+    T: Float + Zero + IsClose<Tol = T>,
+{
+    a < b && !nearly_equal(a, b, rel_tol, abs_tol)
+}
+
+/// Whether `a` is nearly less than or equal to `b` under the given tolerances
+///
+/// This is true if and only if `a < b` or `a` is nearly equal to `b`.
+pub fn nearly_less_equal<T>(a: T, b: T, rel_tol: T, abs_tol: T) -> bool
+where
+    T: Float + Zero + IsClose<Tol = T>,
+{
+    a < b || nearly_equal(a, b, rel_tol, abs_tol)
+}
+
+/// Whether `a` is nearly greater than `b` under the given tolerances
+///
+/// This is true if and only if `a > b` and `a` is *not* nearly equal to `b`.
+pub fn nearly_greater<T>(a: T, b: T, rel_tol: T, abs_tol: T) -> bool
+where
+    T: Float + Zero + IsClose<Tol = T>,
+{
+    a > b && !nearly_equal(a, b, rel_tol, abs_tol)
+}
+
+/// Whether `a` is nearly greater than or equal to `b` under the given tolerances
+///
+/// This is true if and only if `a > b` or `a` is nearly equal to `b`.
+pub fn nearly_greater_equal<T>(a: T, b: T, rel_tol: T, abs_tol: T) -> bool
+where
+    T: Float + Zero + IsClose<Tol = T>,
+{
+    a > b || nearly_equal(a, b, rel_tol, abs_tol)
+}
+
+/// Default relative and absolute tolerances used by the tolerance-aware
+/// comparison macros when no explicit tolerances are supplied.
+///
+/// The defaults are a small per-type value (`1e-6` for `f32`, `1e-9` for `f64`)
+/// in the spirit of the `assert_approx_eq!` macros found elsewhere in the
+/// ecosystem.
+pub trait DefaultTolerance: Float {
+    /// The default relative tolerance for this floating-point type.
+    fn default_rel_tol() -> Self;
+    /// The default absolute tolerance for this floating-point type.
+    fn default_abs_tol() -> Self;
+}
+
+impl DefaultTolerance for f32 {
+    fn default_rel_tol() -> f32 {
+        1e-6
+    }
+    fn default_abs_tol() -> f32 {
+        1e-6
     }
 }
 
+impl DefaultTolerance for f64 {
+    fn default_rel_tol() -> f64 {
+        1e-9
+    }
+    fn default_abs_tol() -> f64 {
+        1e-9
+    }
+}
+
+/// Returns the crate-level default `(rel_tol, abs_tol)` pair for `T`.
+///
+/// Used by [`nearly!`] to fill in tolerances that were omitted at the call site.
+#[doc(hidden)]
+pub fn default_tolerances<T>() -> (T, T)
+where
+    T: DefaultTolerance,
+{
+    (T::default_rel_tol(), T::default_abs_tol())
+}
+
+/// Evaluates a comparison between two floats under tolerance.
+///
+/// The macro parses a single comparison expression — one of `a == b`, `a != b`,
+/// `a < b`, `a <= b`, `a > b` or `a >= b` — and evaluates it tolerantly: `==`
+/// is [`nearly_equal`], `!=` its negation, and the ordering operators treat
+/// values that are within tolerance as equal (so `a < b` is false when `a` and
+/// `b` are nearly equal). The tolerances may be supplied as trailing
+/// arguments; when omitted the crate-level [`DefaultTolerance`] values are used.
+///
+/// ```
+/// # use unit_test_utils::nearly;
+/// assert!(nearly!(1.0 < 2.0));
+/// assert!(nearly!(1.0 <= 1.0 + 1e-12));
+/// assert!(nearly!(1.0 == 1.0 + 1e-7, 1e-6, 1e-6));
+/// assert!(!nearly!(1.0 < 1.0 + 1e-12));
+/// ```
+#[macro_export]
+macro_rules! nearly {
+    ($($cmp:tt)+) => {
+        $crate::__nearly_scan!([] $($cmp)+)
+    };
+}
+
+/// Token-muncher backing [`nearly!`]: accumulates the left operand until it
+/// reaches the comparison operator, then hands off to [`__nearly_rhs`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nearly_scan {
+    ([$($lhs:tt)*] == $($rest:tt)+) => {
+        $crate::__nearly_rhs!(eq [$($lhs)*] [] $($rest)+)
+    };
+    ([$($lhs:tt)*] != $($rest:tt)+) => {
+        $crate::__nearly_rhs!(ne [$($lhs)*] [] $($rest)+)
+    };
+    ([$($lhs:tt)*] <= $($rest:tt)+) => {
+        $crate::__nearly_rhs!(le [$($lhs)*] [] $($rest)+)
+    };
+    ([$($lhs:tt)*] >= $($rest:tt)+) => {
+        $crate::__nearly_rhs!(ge [$($lhs)*] [] $($rest)+)
+    };
+    ([$($lhs:tt)*] < $($rest:tt)+) => {
+        $crate::__nearly_rhs!(lt [$($lhs)*] [] $($rest)+)
+    };
+    ([$($lhs:tt)*] > $($rest:tt)+) => {
+        $crate::__nearly_rhs!(gt [$($lhs)*] [] $($rest)+)
+    };
+    ([$($lhs:tt)*] $head:tt $($rest:tt)+) => {
+        $crate::__nearly_scan!([$($lhs)* $head] $($rest)+)
+    };
+}
+
+/// Token-muncher backing [`nearly!`]: accumulates the right operand until it
+/// reaches the optional trailing `, rel_tol, abs_tol`, then dispatches.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nearly_rhs {
+    ($op:ident [$($lhs:tt)*] [$($rhs:tt)*] , $rel:expr, $abs:expr $(,)?) => {
+        $crate::__nearly_eval!($op $($lhs)*, $($rhs)*, $rel, $abs)
+    };
+    ($op:ident [$($lhs:tt)*] [$($rhs:tt)*] $head:tt $($rest:tt)+) => {
+        $crate::__nearly_rhs!($op [$($lhs)*] [$($rhs)* $head] $($rest)+)
+    };
+    ($op:ident [$($lhs:tt)*] [$($rhs:tt)*] $last:tt) => {{
+        let (rel_tol, abs_tol) = $crate::default_tolerances();
+        $crate::__nearly_eval!($op $($lhs)*, $($rhs)* $last, rel_tol, abs_tol)
+    }};
+}
+
+/// Maps a parsed comparison operator onto the matching tolerant comparator.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nearly_eval {
+    (eq $a:expr, $b:expr, $rel:expr, $abs:expr) => {
+        $crate::nearly_equal($a, $b, $rel, $abs)
+    };
+    (ne $a:expr, $b:expr, $rel:expr, $abs:expr) => {
+        !$crate::nearly_equal($a, $b, $rel, $abs)
+    };
+    (lt $a:expr, $b:expr, $rel:expr, $abs:expr) => {
+        $crate::nearly_less($a, $b, $rel, $abs)
+    };
+    (le $a:expr, $b:expr, $rel:expr, $abs:expr) => {
+        $crate::nearly_less_equal($a, $b, $rel, $abs)
+    };
+    (gt $a:expr, $b:expr, $rel:expr, $abs:expr) => {
+        $crate::nearly_greater($a, $b, $rel, $abs)
+    };
+    (ge $a:expr, $b:expr, $rel:expr, $abs:expr) => {
+        $crate::nearly_greater_equal($a, $b, $rel, $abs)
+    };
+}
+
 /// Asserts that two numbers are nearly equal
 ///
 /// ## Arguments
@@ -88,9 +413,9 @@ where
 ///
 /// The function panics if the two floating-point numbers are not almost equal to one
 /// another up to the specified tolerances
-pub fn assert_nearly_equal<T>(a: T, b: T, rel_tol: T, abs_tol: T, msg: &'static str)
+pub fn assert_nearly_equal<T>(a: T, b: T, rel_tol: T::Tol, abs_tol: T::Tol, msg: &'static str)
 where
-    T: Float + Zero,
+    T: IsClose,
 {
     assert!(nearly_equal(a, b, rel_tol, abs_tol), "{}", msg);
 }
@@ -116,9 +441,10 @@ where
 /// - if the specified relative or absolute tolerance is not positive and
 /// - if the two arrays have different lengths
 ///
-pub fn nearly_equal_array<T>(a: &[T], b: &[T], rel_tol: T, abs_tol: T) -> bool
+pub fn nearly_equal_array<T>(a: &[T], b: &[T], rel_tol: T::Tol, abs_tol: T::Tol) -> bool
 where
-    T: Float + Zero,
+    T: IsClose + Copy,
+    T::Tol: Copy,
 {
     assert!(a.len() == b.len());
     for (&a, &b) in a.iter().zip(b.iter()) {
@@ -129,10 +455,35 @@ where
     true
 }
 
+/// Checks whether two arrays are element-wise nearly equal using a single
+/// tolerance `eps` for both the relative and absolute tolerance.
+///
+/// The array-level counterpart of [`nearly_equal_eps`].
+pub fn nearly_equal_array_eps<T>(a: &[T], b: &[T], eps: T::Tol) -> bool
+where
+    T: IsClose + Copy,
+    T::Tol: Copy,
+{
+    nearly_equal_array(a, b, eps, eps)
+}
+
+/// Checks whether two arrays are element-wise nearly equal using the crate-level
+/// default tolerances for their type (see [`DefaultTolerance`]).
+///
+/// The array-level counterpart of [`nearly_equal_default`].
+pub fn nearly_equal_array_default<T>(a: &[T], b: &[T]) -> bool
+where
+    T: IsClose + Copy,
+    T::Tol: DefaultTolerance,
+{
+    nearly_equal_array(a, b, T::Tol::default_rel_tol(), T::Tol::default_abs_tol())
+}
+
 /// Asserts that two given arrays are almost equal
-pub fn assert_nearly_equal_array<T>(a: &[T], b: &[T], rel_tol: T, abs_tol: T, msg: &'static str)
+pub fn assert_nearly_equal_array<T>(a: &[T], b: &[T], rel_tol: T::Tol, abs_tol: T::Tol, msg: &'static str)
 where
-    T: Float + Zero,
+    T: IsClose + Copy,
+    T::Tol: Copy,
 {
     assert!(a.len() == b.len());
     a.iter()
@@ -240,6 +591,171 @@ where
     }
 }
 
+/// Panic helper backing [`assert_nearly_eq!`] with a value-printing message.
+///
+/// This is an implementation detail of the macro and is marked `#[track_caller]`
+/// so that the reported panic location is the macro call site (i.e., the failing
+/// test) rather than somewhere inside this crate.
+#[doc(hidden)]
+#[track_caller]
+pub fn nearly_eq_panic<T>(
+    a: T,
+    b: T,
+    rel_tol: T,
+    abs_tol: T,
+    msg: Option<std::fmt::Arguments<'_>>,
+) -> !
+where
+    T: Float + std::fmt::Display,
+{
+    let abs_diff = (a - b).abs();
+    match msg {
+        Some(msg) => panic!(
+            "assertion failed: `nearly_eq!(left, right)`: {}\n  left: `{}`\n right: `{}`\n  diff: `{}`\n  rel_tol: `{}`, abs_tol: `{}`",
+            msg, a, b, abs_diff, rel_tol, abs_tol
+        ),
+        None => panic!(
+            "assertion failed: `nearly_eq!(left, right)`\n  left: `{}`\n right: `{}`\n  diff: `{}`\n  rel_tol: `{}`, abs_tol: `{}`",
+            a, b, abs_diff, rel_tol, abs_tol
+        ),
+    }
+}
+
+/// Panic helper backing [`assert_nearly_eq_array!`] with a value-printing message.
+///
+/// Like [`nearly_eq_panic`] this is `#[track_caller]`. It reports either the
+/// length mismatch or the first index at which the two arrays are not nearly
+/// equal, together with the offending values and tolerances.
+#[doc(hidden)]
+#[track_caller]
+pub fn nearly_eq_array_panic<T>(
+    a: &[T],
+    b: &[T],
+    rel_tol: T,
+    abs_tol: T,
+    msg: Option<std::fmt::Arguments<'_>>,
+) -> !
+where
+    T: Float + Zero + std::fmt::Display + IsClose<Tol = T>,
+{
+    if a.len() != b.len() {
+        match msg {
+            Some(msg) => panic!(
+                "assertion failed: `nearly_eq_array!(left, right)`: {}\n  left and right have different lengths: {} vs {}",
+                msg,
+                a.len(),
+                b.len()
+            ),
+            None => panic!(
+                "assertion failed: `nearly_eq_array!(left, right)`\n  left and right have different lengths: {} vs {}",
+                a.len(),
+                b.len()
+            ),
+        }
+    }
+    for (idx, (&ai, &bi)) in a.iter().zip(b.iter()).enumerate() {
+        if !nearly_equal(ai, bi, rel_tol, abs_tol) {
+            let abs_diff = (ai - bi).abs();
+            match msg {
+                Some(msg) => panic!(
+                    "assertion failed: `nearly_eq_array!(left, right)`: {}\n  arrays differ at index {}\n  left: `{}`\n right: `{}`\n  diff: `{}`\n  rel_tol: `{}`, abs_tol: `{}`",
+                    msg, idx, ai, bi, abs_diff, rel_tol, abs_tol
+                ),
+                None => panic!(
+                    "assertion failed: `nearly_eq_array!(left, right)`\n  arrays differ at index {}\n  left: `{}`\n right: `{}`\n  diff: `{}`\n  rel_tol: `{}`, abs_tol: `{}`",
+                    idx, ai, bi, abs_diff, rel_tol, abs_tol
+                ),
+            }
+        }
+    }
+    unreachable!("nearly_eq_array_panic invoked on nearly-equal arrays")
+}
+
+/// Asserts that two numbers are nearly equal, printing both values, their
+/// absolute difference and the tolerances on failure.
+///
+/// This is the value-printing counterpart of [`assert_nearly_equal`]: on
+/// failure the panic points at the call site (via `#[track_caller]`) and, like
+/// the standard [`assert_eq!`], an optional trailing `format!`-style message is
+/// appended.
+///
+/// ```
+/// # use unit_test_utils::assert_nearly_eq;
+/// assert_nearly_eq!(1.0, 1.0 + 1e-9, 1e-6, 1e-6);
+/// assert_nearly_eq!(1.0, 1.0 + 1e-9, 1e-6, 1e-6, "iteration {}", 3);
+/// ```
+#[macro_export]
+macro_rules! assert_nearly_eq {
+    ($a:expr, $b:expr $(,)?) => {{
+        let (a, b) = ($a, $b);
+        if !$crate::nearly_equal_default(a, b) {
+            let (rel_tol, abs_tol) = $crate::default_tolerances();
+            $crate::nearly_eq_panic(a, b, rel_tol, abs_tol, ::core::option::Option::None);
+        }
+    }};
+    ($a:expr, $b:expr, $rel_tol:expr, $abs_tol:expr $(,)?) => {{
+        let (a, b, rel_tol, abs_tol) = ($a, $b, $rel_tol, $abs_tol);
+        if !$crate::nearly_equal(a, b, rel_tol, abs_tol) {
+            $crate::nearly_eq_panic(a, b, rel_tol, abs_tol, ::core::option::Option::None);
+        }
+    }};
+    ($a:expr, $b:expr, $rel_tol:expr, $abs_tol:expr, $($arg:tt)+) => {{
+        let (a, b, rel_tol, abs_tol) = ($a, $b, $rel_tol, $abs_tol);
+        if !$crate::nearly_equal(a, b, rel_tol, abs_tol) {
+            $crate::nearly_eq_panic(
+                a,
+                b,
+                rel_tol,
+                abs_tol,
+                ::core::option::Option::Some(::core::format_args!($($arg)+)),
+            );
+        }
+    }};
+}
+
+/// Asserts that two arrays are element-wise nearly equal, printing the first
+/// offending index, both values, their difference and the tolerances on
+/// failure.
+///
+/// This is the value-printing counterpart of [`assert_nearly_equal_array`] and
+/// mirrors [`assert_nearly_eq!`]: the panic points at the call site and an
+/// optional trailing `format!`-style message is appended.
+///
+/// ```
+/// # use unit_test_utils::assert_nearly_eq_array;
+/// let x = [1.0, 2.0, 3.0];
+/// let y = [1.0, 2.0 + 1e-9, 3.0];
+/// assert_nearly_eq_array!(&x, &y, 1e-6, 1e-6);
+/// ```
+#[macro_export]
+macro_rules! assert_nearly_eq_array {
+    ($a:expr, $b:expr $(,)?) => {{
+        let (a, b) = ($a, $b);
+        if a.len() != b.len() || !$crate::nearly_equal_array_default(a, b) {
+            let (rel_tol, abs_tol) = $crate::default_tolerances();
+            $crate::nearly_eq_array_panic(a, b, rel_tol, abs_tol, ::core::option::Option::None);
+        }
+    }};
+    ($a:expr, $b:expr, $rel_tol:expr, $abs_tol:expr $(,)?) => {{
+        let (a, b, rel_tol, abs_tol) = ($a, $b, $rel_tol, $abs_tol);
+        if a.len() != b.len() || !$crate::nearly_equal_array(a, b, rel_tol, abs_tol) {
+            $crate::nearly_eq_array_panic(a, b, rel_tol, abs_tol, ::core::option::Option::None);
+        }
+    }};
+    ($a:expr, $b:expr, $rel_tol:expr, $abs_tol:expr, $($arg:tt)+) => {{
+        let (a, b, rel_tol, abs_tol) = ($a, $b, $rel_tol, $abs_tol);
+        if a.len() != b.len() || !$crate::nearly_equal_array(a, b, rel_tol, abs_tol) {
+            $crate::nearly_eq_array_panic(
+                a,
+                b,
+                rel_tol,
+                abs_tol,
+                ::core::option::Option::Some(::core::format_args!($($arg)+)),
+            );
+        }
+    }};
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -400,4 +916,184 @@ mod tests {
         let y = [0.0, 1.0, 1.0 + 4e-16, -100.0];
         assert_all_le(&y, 1.0, "y");
     }
+
+    #[test]
+    fn macro_nearly_eq_passes() {
+        assert_nearly_eq!(1.0, 1.0 + 1e-9, 1e-6, 1e-6);
+        assert_nearly_eq!(1000.0_f32, 1001.0_f32, 0.01, 1.0, "custom {}", 7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn macro_nearly_eq_fails() {
+        assert_nearly_eq!(1.0, 2.0, 1e-6, 1e-6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn macro_nearly_eq_fails_with_message() {
+        assert_nearly_eq!(1.0, 2.0, 1e-6, 1e-6, "values diverged at step {}", 4);
+    }
+
+    #[test]
+    fn macro_nearly_eq_array_passes() {
+        let x = [1.0, 2.0, 3.0];
+        let y = [1.0, 2.0 + 1e-9, 3.0];
+        assert_nearly_eq_array!(&x, &y, 1e-6, 1e-6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn macro_nearly_eq_array_fails() {
+        let x = [1.0, 2.0, 3.0];
+        let y = [1.0, 2.5, 3.0];
+        assert_nearly_eq_array!(&x, &y, 1e-6, 1e-6, "row {}", 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn macro_nearly_eq_array_length_mismatch() {
+        let x = [1.0, 2.0, 3.0];
+        let y = [1.0, 2.0];
+        assert_nearly_eq_array!(&x, &y, 1e-6, 1e-6);
+    }
+
+    #[test]
+    fn nearly_macro_equality() {
+        assert!(nearly!(1.0 == 1.0 + 1e-7, 1e-6, 1e-6));
+        assert!(nearly!(1.0 != 2.0, 1e-6, 1e-6));
+        assert!(!nearly!(1.0 == 2.0, 1e-6, 1e-6));
+    }
+
+    #[test]
+    fn nearly_macro_ordering() {
+        assert!(nearly!(1.0 < 2.0, 1e-6, 1e-6));
+        assert!(nearly!(2.0 > 1.0, 1e-6, 1e-6));
+        assert!(nearly!(1.0 <= 1.0 + 1e-7, 1e-6, 1e-6));
+        assert!(nearly!(1.0 >= 1.0 - 1e-7, 1e-6, 1e-6));
+        // values within tolerance are neither strictly less nor strictly greater
+        assert!(!nearly!(1.0 < 1.0 + 1e-7, 1e-6, 1e-6));
+        assert!(!nearly!(1.0 > 1.0 - 1e-7, 1e-6, 1e-6));
+    }
+
+    #[test]
+    fn nearly_macro_default_tolerances() {
+        assert!(nearly!(1.0 == 1.0 + 1e-12));
+        assert!(nearly!(1.0 < 2.0));
+        assert!(!nearly!(1.0 < 1.0 + 1e-12));
+        assert!(nearly!(1.0_f32 == 1.0_f32 + 1e-7));
+    }
+
+    #[test]
+    fn is_close_trait_matches_free_fn() {
+        assert!(1.0.is_close(1.0 + 1e-7, 1e-6, 1e-6));
+        assert!(!(1e-8_f64).is_close(1e-5, 1e-6, 1e-6));
+    }
+
+    #[test]
+    fn is_close_with_nan() {
+        let nan = std::f64::NAN;
+        // opt-in: two NaNs compare equal
+        assert!(nan.is_close_with_nan(nan, 0.1, 0.1));
+        // a single NaN still fails
+        assert!(!nan.is_close_with_nan(1.0, 0.1, 0.1));
+        assert!(!(1.0_f64).is_close_with_nan(nan, 0.1, 0.1));
+        // default behavior is unchanged
+        assert!(!nan.is_close(nan, 0.1, 0.1));
+    }
+
+    #[test]
+    fn complex_nearly_equal() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(1.0 + 1e-9, 2.0 - 1e-9);
+        assert!(nearly_equal(a, b, 1e-6, 1e-6));
+
+        let c = Complex::new(1.0, 2.5);
+        assert!(!nearly_equal(a, c, 1e-6, 1e-6));
+    }
+
+    #[test]
+    fn complex_nan_rejected() {
+        let a = Complex::new(std::f64::NAN, 0.0);
+        let b = Complex::new(0.0, 0.0);
+        assert!(!nearly_equal(a, b, 0.1, 0.1));
+        // but the NaN-equality variant accepts two all-NaN components
+        let c = Complex::new(std::f64::NAN, std::f64::NAN);
+        assert!(c.is_close_with_nan(c, 0.1, 0.1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn assert_complex_not_equal() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(1.0, 3.0);
+        assert_nearly_equal(a, b, 1e-6, 1e-6, "complex numbers not equal");
+    }
+
+    #[test]
+    fn complex_arrays_equal() {
+        let x = [Complex::new(1.0, 0.0), Complex::new(0.0, 1.0)];
+        let y = [Complex::new(1.0 + 1e-9, 0.0), Complex::new(0.0, 1.0 - 1e-9)];
+        assert!(nearly_equal_array(&x, &y, 1e-6, 1e-6));
+        assert_nearly_equal_array(&x, &y, 1e-6, 1e-6, "complex arrays");
+    }
+
+    #[test]
+    fn no_overflow_false_positive_near_max() {
+        // Two wildly different large numbers: the relative constraint must still
+        // reject them even though the absolute tolerance is enormous.
+        let a = std::f64::MAX;
+        let b = a / 2.0;
+        assert!(!nearly_equal(a, b, 1e-6, std::f64::MAX));
+    }
+
+    #[test]
+    fn rel_tol_above_one_near_max() {
+        // `rel_tol > 1` makes `rel_tol * max` overflow to infinity near the top
+        // of the range; the divided-form guard must still give the right answer.
+        let a = std::f64::MAX;
+        let b = a - a * 0.1; // 10% away
+        assert!(nearly_equal(a, b, 2.0, std::f64::MAX));
+        assert!(!nearly_equal(a, b, 0.05, std::f64::MAX));
+    }
+
+    #[test]
+    fn subnormal_no_false_negative() {
+        let a = std::f64::MIN_POSITIVE;
+        let sub = a / 2.0; // a subnormal perturbation
+        assert!(nearly_equal(a, a + sub, 1e-6, std::f64::MIN_POSITIVE));
+    }
+
+    #[test]
+    fn nearly_equal_eps_and_default() {
+        assert!(nearly_equal_eps(1.0, 1.0 + 1e-7, 1e-6));
+        assert!(!nearly_equal_eps(1.0, 2.0, 1e-6));
+        assert!(nearly_equal_default(1.0, 1.0 + 1e-12));
+        assert!(!nearly_equal_default(1.0, 1.1));
+        assert!(nearly_equal_default(1.0_f32, 1.0_f32 + 1e-7));
+    }
+
+    #[test]
+    fn nearly_equal_array_eps_and_default() {
+        let x = [1.0, 2.0, 3.0];
+        let y = [1.0, 2.0 + 1e-7, 3.0];
+        assert!(nearly_equal_array_eps(&x, &y, 1e-6));
+
+        let z = [1.0, 2.0 + 1e-12, 3.0];
+        assert!(nearly_equal_array_default(&x, &z));
+    }
+
+    #[test]
+    fn assert_macros_default_tolerances() {
+        assert_nearly_eq!(1.0, 1.0 + 1e-12);
+        let x = [1.0, 2.0];
+        let y = [1.0, 2.0 + 1e-12];
+        assert_nearly_eq_array!(&x, &y);
+    }
+
+    #[test]
+    #[should_panic]
+    fn assert_macro_default_tolerance_fails() {
+        assert_nearly_eq!(1.0, 2.0);
+    }
 }